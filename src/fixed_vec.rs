@@ -1,77 +1,150 @@
-use std::alloc::{Layout, alloc, dealloc, handle_alloc_error};
-use std::fmt::{self, Debug, Formatter};
+use allocator_api2::alloc::{Allocator, Global};
+use std::alloc::{Layout, handle_alloc_error};
+use std::error::Error;
+use std::fmt::{self, Debug, Display, Formatter};
 use std::iter::FromIterator;
-use std::ops::{Deref, DerefMut};
+use std::ops::{Bound, Deref, DerefMut, RangeBounds};
 use std::ptr::{NonNull, drop_in_place, slice_from_raw_parts_mut};
 use std::slice;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering::{Acquire, Relaxed, Release};
 
+mod drain;
+mod extract_if;
+mod is_zero;
 mod iter;
+pub use drain::Drain;
+pub use extract_if::ExtractIf;
 pub use iter::IntoIter;
 
+use is_zero::value_is_zero;
+
+/// The error returned by [`FixedVec::try_new`] and [`FixedVec::try_realloc`]
+/// when the requested allocation cannot be satisfied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryReserveError {
+    /// The requested capacity's memory layout would overflow `isize::MAX`
+    /// bytes.
+    CapacityOverflow,
+    /// The allocator returned an error (e.g. the allocation request could
+    /// not be satisfied).
+    AllocError {
+        /// The layout that the allocator failed to provide.
+        layout: Layout,
+    },
+}
+
+impl Display for TryReserveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TryReserveError::CapacityOverflow => {
+                write!(f, "capacity overflow: layout would exceed `isize::MAX` bytes")
+            }
+            TryReserveError::AllocError { layout } => {
+                write!(f, "memory allocation of {} bytes failed", layout.size())
+            }
+        }
+    }
+}
+
+impl Error for TryReserveError {}
+
 /// A thread safe [`Vec`]-like structure that never implicitly reallocates.
 ///
 /// Because it uses atomics and does not reallocate, [`FixedVec::push`] does not
 /// require locks or a mutable reference to self.
-pub struct FixedVec<T> {
+///
+/// `FixedVec` is generic over an [`Allocator`], defaulting to [`Global`], so it
+/// can be placed in a bump/arena or shared-memory allocator. Because this
+/// structure never implicitly reallocates, its allocation behavior stays
+/// entirely predictable under a custom allocator.
+pub struct FixedVec<T, A: Allocator = Global> {
     ptr: NonNull<T>,
     next_idx: AtomicUsize,
     len: AtomicUsize,
     cap: usize,
+    alloc: A,
 }
 
 // SAFETY: operations on the same value are atomic.
-unsafe impl<T: Send> Send for FixedVec<T> {}
+unsafe impl<T: Send, A: Allocator + Send> Send for FixedVec<T, A> {}
 
 // SAFETY: addresses are all based on the atomic length and unmodified pointer.
 // They cannot overlap.
-unsafe impl<T: Sync> Sync for FixedVec<T> {}
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for FixedVec<T, A> {}
 
 impl<T> FixedVec<T> {
     #[inline]
     pub fn new(capacity: usize) -> Self {
-        let ptr;
-        let layout = Layout::array::<T>(capacity).expect("Layout overflow");
-        if layout.size() == 0 {
-            ptr = NonNull::dangling();
-        } else {
-            // SAFETY: we check for a zero-sized type or capacity above.
-            let raw_ptr = unsafe { alloc(layout) } as *mut T;
+        Self::new_in(capacity, Global)
+    }
 
-            if raw_ptr.is_null() {
-                handle_alloc_error(layout);
-            }
+    /// Like [`FixedVec::new`], but returns a [`TryReserveError`] instead of
+    /// panicking or aborting when the allocation cannot be satisfied.
+    #[inline]
+    pub fn try_new(capacity: usize) -> Result<Self, TryReserveError> {
+        Self::try_new_in(capacity, Global)
+    }
+}
 
-            // SAFETY: we check for a null pointer above.
-            ptr = unsafe { NonNull::new_unchecked(raw_ptr) };
-        }
+impl<T: Clone + 'static> FixedVec<T> {
+    /// Creates a vector of length `n` filled with clones of `value`.
+    ///
+    /// When `T` is one of the types whose all-zero bit pattern is a valid
+    /// value (integers, `bool`, `char` and floats) and `value` itself is
+    /// zero, the backing memory is obtained pre-zeroed in a single
+    /// allocation rather than written element by element.
+    ///
+    /// Pointer-shaped types (`Option<NonNull<_>>`, `*const _`, `*mut _`) are
+    /// also conceptually zero-able, but aren't recognized by the fast path:
+    /// it identifies candidate types at runtime (see below), which can only
+    /// enumerate a fixed, concrete list and can't match a family generic
+    /// over an unbounded pointee. `from_elem` still produces the correct
+    /// result for them, just via the element-by-element clone loop.
+    ///
+    /// `T: 'static` is required because recognizing a zero value is done at
+    /// runtime via [`std::any::Any`] rather than a compile-time trait bound
+    /// — stable Rust has no specialization to pick a zero-check impl for an
+    /// otherwise-unconstrained `T`. This is stricter than `Vec`'s analogous
+    /// `vec![value; n]`, which has no such requirement.
+    #[inline]
+    pub fn from_elem(value: T, n: usize) -> Self {
+        Self::from_elem_in(value, n, Global)
+    }
+}
 
-        Self {
-            ptr,
-            next_idx: AtomicUsize::new(0),
-            len: AtomicUsize::new(0),
-            cap: capacity,
+impl<T, A: Allocator> FixedVec<T, A> {
+    #[inline]
+    pub fn new_in(capacity: usize, alloc: A) -> Self {
+        match Self::try_new_in(capacity, alloc) {
+            Ok(vec) => vec,
+            Err(TryReserveError::CapacityOverflow) => panic!("capacity overflow"),
+            Err(TryReserveError::AllocError { layout }) => handle_alloc_error(layout),
         }
     }
 
+    /// Like [`FixedVec::new_in`], but returns a [`TryReserveError`] instead
+    /// of panicking or aborting when the allocation cannot be satisfied.
     #[inline]
-    pub fn realloc(&mut self) {
-        let len = self.len();
-        let new_cap = if self.cap == 0 { 1 } else { self.cap * 2 };
-        let new_vec = Self::new(new_cap);
-
-        unsafe {
-            new_vec.ptr.copy_from_nonoverlapping(self.ptr, len);
-        }
+    pub fn try_new_in(capacity: usize, alloc: A) -> Result<Self, TryReserveError> {
+        let layout = Layout::array::<T>(capacity).map_err(|_| TryReserveError::CapacityOverflow)?;
 
-        new_vec.next_idx.store(len, Relaxed);
-        new_vec.len.store(len, Release);
+        let ptr = if layout.size() == 0 {
+            NonNull::dangling()
+        } else {
+            alloc
+                .allocate(layout)
+                .map_err(|_| TryReserveError::AllocError { layout })?
+                .cast()
+        };
 
-        // We move new_vec into self and get the old self, so we can drop the old one.
-        let old_vec = std::mem::replace(self, new_vec);
-        old_vec.len.store(0, Relaxed);
-        // old_vec will be dropped at the end of this scope, deallocating its memory.
+        Ok(Self {
+            ptr,
+            next_idx: AtomicUsize::new(0),
+            len: AtomicUsize::new(0),
+            cap: capacity,
+            alloc,
+        })
     }
 
     #[inline]
@@ -85,6 +158,12 @@ impl<T> FixedVec<T> {
         self.cap
     }
 
+    /// Returns a reference to the allocator backing this vector.
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
     #[inline]
     pub fn push(&self, value: T) -> Result<(), T> {
         // Using `Relaxed` since we don't care what goes on at previous indices when
@@ -122,9 +201,228 @@ impl<T> FixedVec<T> {
         // SAFETY: all elements up to `len` have been initialized and are of type `T`.
         unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len()) }
     }
+
+    /// Removes and yields elements for which `pred` returns `true`, keeping
+    /// the rest in place (shifted to close the gaps) and preserving the
+    /// backing allocation.
+    ///
+    /// If the returned [`ExtractIf`] is dropped before it is fully consumed,
+    /// the remaining elements are still scanned against `pred` and the
+    /// vector is left in a consistent, compacted state.
+    #[inline]
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, T, A, F>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        ExtractIf::new(self, pred)
+    }
+
+    /// Removes the elements in `range`, yielding them by value while keeping
+    /// the backing allocation.
+    ///
+    /// If the returned [`Drain`] is dropped before it is fully consumed, the
+    /// remaining undrained elements are dropped and the tail is still
+    /// shifted down to close the gap. Call [`Drain::keep_rest`] instead to
+    /// keep those elements in the vector rather than dropping them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end, or if the
+    /// end is greater than the vector's length.
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T, A> {
+        let len = self.len();
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n + 1,
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => len,
+        };
+
+        assert!(start <= end, "drain start must not exceed end");
+        assert!(end <= len, "drain end out of bounds");
+
+        Drain::new(self, start, end)
+    }
 }
 
-impl<T> Deref for FixedVec<T> {
+impl<T: Clone + 'static, A: Allocator> FixedVec<T, A> {
+    /// Like [`FixedVec::from_elem`], but allocates in `alloc`. See
+    /// [`FixedVec::from_elem`] for which types take the zeroed-allocation
+    /// fast path and why `T: 'static` is required.
+    pub fn from_elem_in(value: T, n: usize, alloc: A) -> Self {
+        if value_is_zero(&value) {
+            // SAFETY: `value_is_zero` only returns `true` for types whose
+            // all-zero bit pattern is a valid value of that type, so a
+            // zeroed allocation is a legitimate `T` in every slot.
+            return unsafe { Self::zeroed_in(n, alloc) };
+        }
+
+        let vec = Self::new_in(n, alloc);
+        for _ in 0..n {
+            let _ = vec.push(value.clone());
+        }
+        vec
+    }
+
+    /// Allocates `n` slots pre-zeroed and marks them all initialized,
+    /// without running any `T` constructor.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that an all-zero bit pattern is a valid value
+    /// of `T`.
+    unsafe fn zeroed_in(n: usize, alloc: A) -> Self {
+        let layout = match Layout::array::<T>(n) {
+            Ok(layout) => layout,
+            Err(_) => panic!("capacity overflow"),
+        };
+
+        let ptr = if layout.size() == 0 {
+            NonNull::dangling()
+        } else {
+            match alloc.allocate_zeroed(layout) {
+                Ok(ptr) => ptr.cast(),
+                Err(_) => handle_alloc_error(layout),
+            }
+        };
+
+        Self {
+            ptr,
+            next_idx: AtomicUsize::new(n),
+            len: AtomicUsize::new(n),
+            cap: n,
+            alloc,
+        }
+    }
+}
+
+impl<T, A: Allocator + Clone> FixedVec<T, A> {
+    #[inline]
+    pub fn realloc(&mut self) {
+        match self.try_realloc() {
+            Ok(()) => {}
+            Err(TryReserveError::CapacityOverflow) => panic!("capacity overflow"),
+            Err(TryReserveError::AllocError { layout }) => handle_alloc_error(layout),
+        }
+    }
+
+    /// Like [`FixedVec::realloc`], but returns a [`TryReserveError`] instead
+    /// of panicking or aborting when the new allocation cannot be satisfied.
+    #[inline]
+    pub fn try_realloc(&mut self) -> Result<(), TryReserveError> {
+        let len = self.len();
+        let new_cap = if self.cap == 0 { 1 } else { self.cap * 2 };
+        let new_vec = Self::try_new_in(new_cap, self.alloc.clone())?;
+
+        unsafe {
+            new_vec.ptr.copy_from_nonoverlapping(self.ptr, len);
+        }
+
+        new_vec.next_idx.store(len, Relaxed);
+        new_vec.len.store(len, Release);
+
+        // We move new_vec into self and get the old self, so we can drop the old one.
+        let old_vec = std::mem::replace(self, new_vec);
+        old_vec.len.store(0, Relaxed);
+        // old_vec will be dropped at the end of this scope, deallocating its memory.
+
+        Ok(())
+    }
+
+    /// Reallocates, if needed, to fit at least `needed` elements in a single
+    /// step rather than repeatedly doubling, then copies the existing
+    /// elements across.
+    fn reserve_exact(&mut self, needed: usize) {
+        if needed <= self.cap {
+            return;
+        }
+
+        let len = self.len();
+        let new_vec = Self::new_in(needed, self.alloc.clone());
+
+        unsafe {
+            new_vec.ptr.copy_from_nonoverlapping(self.ptr, len);
+        }
+
+        new_vec.next_idx.store(len, Relaxed);
+        new_vec.len.store(len, Release);
+
+        let old_vec = std::mem::replace(self, new_vec);
+        old_vec.len.store(0, Relaxed);
+    }
+
+    /// Bulk `Extend` fast path used when `&mut self` gives us exclusive
+    /// access and the iterator reports an exact element count: we grow once
+    /// if needed, then write straight through the pointer with no
+    /// per-element atomic ops, finally syncing `len`/`next_idx` once.
+    ///
+    /// Falls back to the push-per-item loop for iterators whose `size_hint`
+    /// bounds differ, including those with no upper bound.
+    fn extend_from_iter<I: Iterator<Item = T>>(&mut self, mut iter: I) {
+        let (lower, upper) = iter.size_hint();
+
+        if upper != Some(lower) {
+            for item in iter {
+                if let Err(item) = self.push(item) {
+                    self.realloc();
+                    let _ = self.push(item);
+                }
+            }
+            return;
+        }
+
+        let start = self.len();
+        self.reserve_exact(start + lower);
+
+        // Guarantees `len`/`next_idx` are synced to however many elements
+        // actually got written, even if the iterator panics partway through.
+        struct LenGuard<'a, T, A: Allocator> {
+            vec: &'a FixedVec<T, A>,
+            written: usize,
+        }
+
+        impl<T, A: Allocator> Drop for LenGuard<'_, T, A> {
+            fn drop(&mut self) {
+                self.vec.next_idx.store(self.written, Relaxed);
+                self.vec.len.store(self.written, Release);
+            }
+        }
+
+        {
+            let mut guard = LenGuard {
+                vec: self,
+                written: start,
+            };
+
+            for i in 0..lower {
+                match iter.next() {
+                    Some(item) => unsafe {
+                        guard.vec.ptr.add(start + i).write(item);
+                        guard.written = start + i + 1;
+                    },
+                    None => break,
+                }
+            }
+        }
+
+        // `size_hint`'s upper bound is only a hint, not a guarantee like
+        // `TrustedLen` would give us: a safe but inaccurate iterator can
+        // still yield more than it claimed. Drain any such stragglers
+        // through the normal fallback instead of silently dropping them.
+        for item in iter {
+            if let Err(item) = self.push(item) {
+                self.realloc();
+                let _ = self.push(item);
+            }
+        }
+    }
+}
+
+impl<T, A: Allocator> Deref for FixedVec<T, A> {
     type Target = [T];
 
     #[inline]
@@ -133,7 +431,7 @@ impl<T> Deref for FixedVec<T> {
     }
 }
 
-impl<T> DerefMut for FixedVec<T> {
+impl<T, A: Allocator> DerefMut for FixedVec<T, A> {
     #[inline]
     fn deref_mut(&mut self) -> &mut Self::Target {
         self.as_mut_slice()
@@ -147,7 +445,7 @@ impl<T> Default for FixedVec<T> {
     }
 }
 
-impl<T: Debug> Debug for FixedVec<T> {
+impl<T: Debug, A: Allocator> Debug for FixedVec<T, A> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         Debug::fmt(&**self, f)
     }
@@ -160,32 +458,22 @@ impl<T> FromIterator<T> for FixedVec<T> {
         let (lower, upper) = iter.size_hint();
         let cap = upper.unwrap_or(lower);
         let mut vec = Self::new(cap);
-        for item in iter {
-            if let Err(item) = vec.push(item) {
-                vec.realloc();
-                let _ = vec.push(item);
-            }
-        }
+        vec.extend_from_iter(iter);
         vec
     }
 }
 
-impl<T> Extend<T> for FixedVec<T> {
+impl<T, A: Allocator + Clone> Extend<T> for FixedVec<T, A> {
     #[inline]
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
-        for item in iter {
-            if let Err(item) = self.push(item) {
-                self.realloc();
-                let _ = self.push(item);
-            }
-        }
+        self.extend_from_iter(iter.into_iter());
     }
 }
 
-impl<T: Clone> Clone for FixedVec<T> {
+impl<T: Clone, A: Allocator + Clone> Clone for FixedVec<T, A> {
     fn clone(&self) -> Self {
         let len = self.len();
-        let new_vec = Self::new(self.cap);
+        let new_vec = Self::new_in(self.cap, self.alloc.clone());
 
         for i in 0..len {
             if let Some(item) = self.get(i) {
@@ -197,22 +485,24 @@ impl<T: Clone> Clone for FixedVec<T> {
     }
 }
 
-impl<T> Drop for FixedVec<T> {
+impl<T, A: Allocator> Drop for FixedVec<T, A> {
     fn drop(&mut self) {
-        struct DropGuard<T> {
+        struct DropGuard<'a, T, A: Allocator> {
             ptr: NonNull<T>,
             cap: usize,
+            alloc: &'a A,
         }
 
-        impl<T> Drop for DropGuard<T> {
+        impl<T, A: Allocator> Drop for DropGuard<'_, T, A> {
             fn drop(&mut self) {
-                dealloc_vec(self.ptr, self.cap);
+                dealloc_vec(self.ptr, self.cap, self.alloc);
             }
         }
 
         let _guard = DropGuard {
             ptr: self.ptr,
             cap: self.cap,
+            alloc: &self.alloc,
         };
 
         // Drop elements.
@@ -226,15 +516,15 @@ impl<T> Drop for FixedVec<T> {
     }
 }
 
-fn dealloc_vec<T>(ptr: NonNull<T>, capacity: usize) {
+fn dealloc_vec<T, A: Allocator>(ptr: NonNull<T>, capacity: usize, alloc: &A) {
     // This should not return an error since this is the same layout as was used for
     // allocation.
     let layout = Layout::array::<T>(capacity).unwrap();
-    unsafe {
-        // We can't deallocate if it's zero-sized.
-        if layout.size() > 0 {
-            // SAFETY: the same layout was used to allocate.
-            dealloc(ptr.as_ptr() as *mut u8, layout);
+    // We can't deallocate if it's zero-sized.
+    if layout.size() > 0 {
+        // SAFETY: the same layout was used to allocate, via the same allocator.
+        unsafe {
+            alloc.deallocate(ptr.cast(), layout);
         }
     }
 }
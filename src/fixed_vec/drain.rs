@@ -0,0 +1,158 @@
+use crate::FixedVec;
+use allocator_api2::alloc::Allocator;
+use std::iter::FusedIterator;
+use std::mem::ManuallyDrop;
+use std::ptr::{self, drop_in_place, slice_from_raw_parts_mut};
+use std::sync::atomic::Ordering::{Relaxed, Release};
+
+/// An iterator produced by [`FixedVec::drain`] that removes a contiguous
+/// range of elements and yields them by value, keeping the backing
+/// allocation.
+pub struct Drain<'a, T, A: Allocator> {
+    vec: &'a mut FixedVec<T, A>,
+    // The front/back cursors of the still-undrained part of the range.
+    start: usize,
+    end: usize,
+    // Where the retained tail (the elements originally after the range)
+    // begins in the backing buffer, and how many of them there are.
+    tail_start: usize,
+    tail_len: usize,
+}
+
+impl<'a, T, A: Allocator> Drain<'a, T, A> {
+    pub(crate) fn new(vec: &'a mut FixedVec<T, A>, start: usize, end: usize) -> Self {
+        let len = vec.len();
+        let tail_start = end;
+        let tail_len = len - end;
+
+        // Shorten the vector's visible length to the start of the drained
+        // range up front, so a leaked `Drain` can't expose stale slots.
+        vec.next_idx.store(start, Relaxed);
+        vec.len.store(start, Release);
+
+        Self {
+            vec,
+            start,
+            end,
+            tail_start,
+            tail_len,
+        }
+    }
+
+    /// Stops draining, keeping the elements that haven't been yielded yet
+    /// (instead of dropping them) and shifting them, along with the
+    /// retained tail, back into place without reallocating.
+    pub fn keep_rest(self) {
+        let this = ManuallyDrop::new(self);
+        let dest = this.vec.len();
+        let unyielded_len = this.end - this.start;
+
+        unsafe {
+            if unyielded_len > 0 {
+                let src = this.vec.ptr.as_ptr().add(this.start);
+                let dst = this.vec.ptr.as_ptr().add(dest);
+                if src != dst {
+                    ptr::copy(src, dst, unyielded_len);
+                }
+            }
+
+            let new_tail_start = dest + unyielded_len;
+            if this.tail_len > 0 && this.tail_start != new_tail_start {
+                let src = this.vec.ptr.as_ptr().add(this.tail_start);
+                let dst = this.vec.ptr.as_ptr().add(new_tail_start);
+                ptr::copy(src, dst, this.tail_len);
+            }
+
+            let new_len = new_tail_start + this.tail_len;
+            this.vec.next_idx.store(new_len, Relaxed);
+            this.vec.len.store(new_len, Release);
+        }
+    }
+}
+
+impl<T, A: Allocator> Iterator for Drain<'_, T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+
+        unsafe {
+            let item_ptr = self.vec.ptr.as_ptr().add(self.start);
+            self.start += 1;
+            Some(ptr::read(item_ptr))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.start;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T, A: Allocator> DoubleEndedIterator for Drain<'_, T, A> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+
+        unsafe {
+            self.end -= 1;
+            let item_ptr = self.vec.ptr.as_ptr().add(self.end);
+            Some(ptr::read(item_ptr))
+        }
+    }
+}
+
+impl<T, A: Allocator> ExactSizeIterator for Drain<'_, T, A> {}
+
+impl<T, A: Allocator> FusedIterator for Drain<'_, T, A> {}
+
+impl<T, A: Allocator> Drop for Drain<'_, T, A> {
+    fn drop(&mut self) {
+        struct TailGuard<'a, T, A: Allocator> {
+            vec: &'a FixedVec<T, A>,
+            dest: usize,
+            tail_start: usize,
+            tail_len: usize,
+        }
+
+        impl<T, A: Allocator> Drop for TailGuard<'_, T, A> {
+            fn drop(&mut self) {
+                // Shift the retained tail down to close the hole left by the
+                // drained range, then restore the length. Runs even if
+                // dropping the undrained elements below panics.
+                if self.tail_len > 0 && self.tail_start != self.dest {
+                    unsafe {
+                        let src = self.vec.ptr.as_ptr().add(self.tail_start);
+                        let dst = self.vec.ptr.as_ptr().add(self.dest);
+                        ptr::copy(src, dst, self.tail_len);
+                    }
+                }
+
+                let new_len = self.dest + self.tail_len;
+                self.vec.next_idx.store(new_len, Relaxed);
+                self.vec.len.store(new_len, Release);
+            }
+        }
+
+        let guard = TailGuard {
+            vec: self.vec,
+            dest: self.vec.len(),
+            tail_start: self.tail_start,
+            tail_len: self.tail_len,
+        };
+
+        // Drop whatever in the drained range was never yielded.
+        unsafe {
+            let remaining = slice_from_raw_parts_mut(
+                self.vec.ptr.as_ptr().add(self.start),
+                self.end - self.start,
+            );
+            drop_in_place(remaining);
+        }
+
+        drop(guard);
+    }
+}
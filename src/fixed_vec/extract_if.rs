@@ -0,0 +1,126 @@
+use crate::FixedVec;
+use allocator_api2::alloc::Allocator;
+use std::ptr;
+use std::sync::atomic::Ordering::{Relaxed, Release};
+
+/// An iterator produced by [`FixedVec::extract_if`] that removes and yields
+/// elements matching a predicate, compacting the surviving elements in place.
+///
+/// The backing allocation is kept; only the vector's length shrinks.
+pub struct ExtractIf<'a, T, A: Allocator, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    vec: &'a mut FixedVec<T, A>,
+    old_len: usize,
+    read: usize,
+    write: usize,
+    pred: F,
+}
+
+impl<'a, T, A: Allocator, F> ExtractIf<'a, T, A, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    pub(crate) fn new(vec: &'a mut FixedVec<T, A>, pred: F) -> Self {
+        let old_len = vec.len();
+
+        // Shrink the length to zero up front, so a leaked `ExtractIf` can't
+        // expose slots that have already been read out or shifted.
+        vec.next_idx.store(0, Relaxed);
+        vec.len.store(0, Release);
+
+        Self {
+            vec,
+            old_len,
+            read: 0,
+            write: 0,
+            pred,
+        }
+    }
+}
+
+impl<T, A: Allocator, F> Iterator for ExtractIf<'_, T, A, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        unsafe {
+            while self.read < self.old_len {
+                let cur = self.vec.ptr.as_ptr().add(self.read);
+                self.read += 1;
+
+                if (self.pred)(&mut *cur) {
+                    return Some(ptr::read(cur));
+                }
+
+                if self.write != self.read - 1 {
+                    let dst = self.vec.ptr.as_ptr().add(self.write);
+                    ptr::copy_nonoverlapping(cur, dst, 1);
+                }
+                self.write += 1;
+            }
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.old_len - self.read))
+    }
+}
+
+impl<T, A: Allocator, F> Drop for ExtractIf<'_, T, A, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    fn drop(&mut self) {
+        struct LenGuard<'a, T, A: Allocator> {
+            vec: &'a FixedVec<T, A>,
+            // Raw pointer rather than a borrow: it is read only after the
+            // scan below (which mutates `write` through `self`) completes or
+            // unwinds, so it never aliases a live `&mut`.
+            write: *const usize,
+        }
+
+        impl<T, A: Allocator> Drop for LenGuard<'_, T, A> {
+            fn drop(&mut self) {
+                // SAFETY: `write` points at the owning `ExtractIf`'s `write`
+                // field, which outlives this guard.
+                let len = unsafe { *self.write };
+                self.vec.next_idx.store(len, Relaxed);
+                self.vec.len.store(len, Release);
+            }
+        }
+
+        let guard = LenGuard {
+            vec: self.vec,
+            write: &self.write,
+        };
+
+        // Finish scanning the tail so elements we never got to yield are
+        // still tested against the predicate, with survivors shifted left
+        // to close the gap. The guard above still fires if `pred` or an
+        // element's `Drop` panics partway through, storing whatever length
+        // was reached so far.
+        unsafe {
+            while self.read < self.old_len {
+                let cur = self.vec.ptr.as_ptr().add(self.read);
+                self.read += 1;
+
+                if (self.pred)(&mut *cur) {
+                    ptr::drop_in_place(cur);
+                } else {
+                    if self.write != self.read - 1 {
+                        let dst = self.vec.ptr.as_ptr().add(self.write);
+                        ptr::copy_nonoverlapping(cur, dst, 1);
+                    }
+                    self.write += 1;
+                }
+            }
+        }
+
+        drop(guard);
+    }
+}
@@ -0,0 +1,122 @@
+use std::any::Any;
+
+/// Types whose all-zero bit pattern is a valid value of that type.
+///
+/// Used by [`FixedVec::from_elem`](crate::FixedVec::from_elem) to recognize
+/// when a zeroed allocation can stand in for writing a clone of the value to
+/// every slot, skipping the per-element write loop entirely.
+pub(crate) trait IsZero {
+    /// Returns `true` if `self` is represented by an all-zero bit pattern.
+    fn is_zero(&self) -> bool;
+}
+
+macro_rules! impl_is_zero_int {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl IsZero for $t {
+                #[inline]
+                fn is_zero(&self) -> bool {
+                    *self == 0
+                }
+            }
+        )*
+    };
+}
+
+impl_is_zero_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl IsZero for bool {
+    #[inline]
+    fn is_zero(&self) -> bool {
+        !*self
+    }
+}
+
+impl IsZero for char {
+    #[inline]
+    fn is_zero(&self) -> bool {
+        *self == '\0'
+    }
+}
+
+impl IsZero for f32 {
+    #[inline]
+    fn is_zero(&self) -> bool {
+        // Compare bit patterns rather than using `==`, since `0.0 == -0.0`
+        // but only `0.0` is actually the all-zero bit pattern, and `NaN`
+        // isn't equal to anything (including itself).
+        self.to_bits() == 0
+    }
+}
+
+impl IsZero for f64 {
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.to_bits() == 0
+    }
+}
+
+macro_rules! check_is_zero {
+    ($value:expr, $($t:ty),* $(,)?) => {
+        $(
+            if let Some(v) = $value.downcast_ref::<$t>() {
+                return IsZero::is_zero(v);
+            }
+        )*
+    };
+}
+
+/// Reports whether `value` is the all-zero bit pattern of a type we know how
+/// to recognize, without requiring `T: IsZero` at the call site.
+///
+/// Rust has no stable specialization, so this can't dispatch through
+/// [`IsZero`] directly for a fully generic `T` (trait selection happens once,
+/// generically, at this function's own type-check, long before `T` is known
+/// to be any particular type). Instead it checks `T`'s [`TypeId`](std::any::TypeId)
+/// against each concretely known zero-able type at runtime via [`Any`], which
+/// *does* resolve per monomorphization, and is the correct tool for the job
+/// on stable Rust.
+pub(crate) fn value_is_zero<T: 'static>(value: &T) -> bool {
+    let value: &dyn Any = value;
+    check_is_zero!(value, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+    check_is_zero!(value, bool, char, f32, f64);
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::value_is_zero;
+
+    #[test]
+    fn recognizes_zero_values() {
+        assert!(value_is_zero(&0u8));
+        assert!(value_is_zero(&0i32));
+        assert!(value_is_zero(&false));
+        assert!(value_is_zero(&'\0'));
+        assert!(value_is_zero(&0.0f32));
+        assert!(value_is_zero(&0.0f64));
+    }
+
+    #[test]
+    fn rejects_non_zero_values() {
+        assert!(!value_is_zero(&1u8));
+        assert!(!value_is_zero(&true));
+        assert!(!value_is_zero(&'a'));
+    }
+
+    #[test]
+    fn rejects_negative_zero_and_nan() {
+        // `-0.0` compares equal to `0.0` but is not the all-zero bit
+        // pattern, and `NaN` isn't equal to anything; both must be
+        // rejected so a zeroed allocation is never substituted for them.
+        assert!(!value_is_zero(&-0.0f32));
+        assert!(!value_is_zero(&-0.0f64));
+        assert!(!value_is_zero(&f32::NAN));
+        assert!(!value_is_zero(&f64::NAN));
+    }
+
+    #[test]
+    fn unrecognized_types_are_never_reported_as_zero() {
+        assert!(!value_is_zero(&String::new()));
+    }
+}
@@ -1,11 +1,12 @@
 use crate::FixedVec;
 use crate::fixed_vec::dealloc_vec;
+use allocator_api2::alloc::{Allocator, Global};
 use std::iter::FusedIterator;
 use std::mem::ManuallyDrop;
-use std::ptr::{NonNull, drop_in_place, slice_from_raw_parts_mut};
+use std::ptr::{self, NonNull, drop_in_place, slice_from_raw_parts_mut};
 use std::slice;
 
-impl<'a, T> IntoIterator for &'a FixedVec<T> {
+impl<'a, T, A: Allocator> IntoIterator for &'a FixedVec<T, A> {
     type Item = &'a T;
     type IntoIter = slice::Iter<'a, T>;
 
@@ -14,7 +15,7 @@ impl<'a, T> IntoIterator for &'a FixedVec<T> {
     }
 }
 
-impl<'a, T> IntoIterator for &'a mut FixedVec<T> {
+impl<'a, T, A: Allocator> IntoIterator for &'a mut FixedVec<T, A> {
     type Item = &'a mut T;
     type IntoIter = slice::IterMut<'a, T>;
 
@@ -23,38 +24,44 @@ impl<'a, T> IntoIterator for &'a mut FixedVec<T> {
     }
 }
 
-impl<T> IntoIterator for FixedVec<T> {
+impl<T, A: Allocator> IntoIterator for FixedVec<T, A> {
     type Item = T;
-    type IntoIter = IntoIter<T>;
+    type IntoIter = IntoIter<T, A>;
 
     fn into_iter(self) -> Self::IntoIter {
-        let iter = Self::IntoIter {
-            ptr: self.ptr,
-            start: 0,
-            end: self.len(),
-            cap: self.capacity(),
-        };
+        let this = ManuallyDrop::new(self);
 
-        let _ = ManuallyDrop::new(self);
-        iter
+        // SAFETY: `this` is wrapped in `ManuallyDrop`, so its destructor
+        // (which would deallocate the buffer and drop its elements) never
+        // runs; reading `alloc` out here does not double-move it.
+        let alloc = unsafe { ptr::read(&this.alloc) };
+
+        IntoIter {
+            ptr: this.ptr,
+            start: 0,
+            end: this.len(),
+            cap: this.cap,
+            alloc,
+        }
     }
 }
 
-pub struct IntoIter<T> {
+pub struct IntoIter<T, A: Allocator = Global> {
     ptr: NonNull<T>,
     start: usize,
     end: usize,
     cap: usize,
+    alloc: A,
 }
 
 // SAFETY: `T` is owned by `IntoIter` and provides no interior mutability of its
 // own, so as long as `T` is Send, `IntoIter` is too.
-unsafe impl<T: Send> Send for IntoIter<T> {}
+unsafe impl<T: Send, A: Allocator + Send> Send for IntoIter<T, A> {}
 
 // SAFETY: `IntoIter` has no public fields or methods which take `&self`.
-unsafe impl<T> Sync for IntoIter<T> {}
+unsafe impl<T, A: Allocator> Sync for IntoIter<T, A> {}
 
-impl<T> Iterator for IntoIter<T> {
+impl<T, A: Allocator> Iterator for IntoIter<T, A> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -86,11 +93,11 @@ impl<T> Iterator for IntoIter<T> {
     }
 }
 
-impl<T> ExactSizeIterator for IntoIter<T> {}
+impl<T, A: Allocator> ExactSizeIterator for IntoIter<T, A> {}
 
-impl<T> FusedIterator for IntoIter<T> {}
+impl<T, A: Allocator> FusedIterator for IntoIter<T, A> {}
 
-impl<T> DoubleEndedIterator for IntoIter<T> {
+impl<T, A: Allocator> DoubleEndedIterator for IntoIter<T, A> {
     fn next_back(&mut self) -> Option<Self::Item> {
         if self.end == self.start {
             return None;
@@ -105,22 +112,24 @@ impl<T> DoubleEndedIterator for IntoIter<T> {
     }
 }
 
-impl<T> Drop for IntoIter<T> {
+impl<T, A: Allocator> Drop for IntoIter<T, A> {
     fn drop(&mut self) {
-        struct DropGuard<T> {
+        struct DropGuard<'a, T, A: Allocator> {
             ptr: NonNull<T>,
             cap: usize,
+            alloc: &'a A,
         }
 
-        impl<T> Drop for DropGuard<T> {
+        impl<T, A: Allocator> Drop for DropGuard<'_, T, A> {
             fn drop(&mut self) {
-                dealloc_vec(self.ptr, self.cap);
+                dealloc_vec(self.ptr, self.cap, self.alloc);
             }
         }
 
         let _guard = DropGuard {
             ptr: self.ptr,
             cap: self.cap,
+            alloc: &self.alloc,
         };
 
         // Drop any remaining initialized elements that haven't been yielded.
@@ -1,9 +1,70 @@
 mod fixed_vec;
-pub use fixed_vec::{FixedVec, IntoIter};
+pub use allocator_api2::alloc::{AllocError, Allocator, Global};
+pub use fixed_vec::{Drain, ExtractIf, FixedVec, IntoIter, TryReserveError};
 
 #[cfg(test)]
 mod tests {
-    use crate::fixed_vec::FixedVec;
+    use crate::fixed_vec::{FixedVec, TryReserveError};
+
+    #[test]
+    fn try_new_reports_capacity_overflow() {
+        let err = FixedVec::<u64>::try_new(usize::MAX).unwrap_err();
+        assert_eq!(err, TryReserveError::CapacityOverflow);
+    }
+
+    #[test]
+    fn new_in_uses_the_given_allocator() {
+        use crate::Global;
+
+        let vec = FixedVec::<u32, Global>::new_in(4, Global);
+        assert_eq!(vec.capacity(), 4);
+        assert_eq!(vec.len(), 0);
+        // `allocator` should hand back the same allocator instance we
+        // constructed the vector with.
+        let _: &Global = vec.allocator();
+    }
+
+    #[test]
+    fn extract_if_finishes_compacting_when_dropped_early() {
+        let mut vec = FixedVec::<i32>::from_iter([1, 2, 3, 4, 5, 6]);
+
+        {
+            let mut extracted = vec.extract_if(|x| *x % 2 == 0);
+            // Only consume the first match, then drop the rest unyielded.
+            assert_eq!(extracted.next(), Some(2));
+        }
+
+        // The drop should still have scanned the remainder, matching (and
+        // dropping) 4 and 6, and compacted the survivors in place.
+        assert_eq!(&*vec, [1, 3, 5]);
+    }
+
+    #[test]
+    fn drain_range_yields_both_ends_and_compacts() {
+        let mut vec = FixedVec::<i32>::from_iter([1, 2, 3, 4, 5, 6]);
+
+        let mut drained = vec.drain(1..4);
+        assert_eq!(drained.next(), Some(2));
+        assert_eq!(drained.next_back(), Some(4));
+        assert_eq!(drained.next(), Some(3));
+        assert_eq!(drained.next(), None);
+        drop(drained);
+
+        assert_eq!(&*vec, [1, 5, 6]);
+    }
+
+    #[test]
+    fn drain_keep_rest_restores_unyielded_elements() {
+        let mut vec = FixedVec::<i32>::from_iter([1, 2, 3, 4, 5, 6]);
+
+        let mut drained = vec.drain(1..4);
+        assert_eq!(drained.next(), Some(2));
+        // Stop here and keep the rest of the drained range (3, 4) instead
+        // of dropping them.
+        drained.keep_rest();
+
+        assert_eq!(&*vec, [1, 3, 4, 5, 6]);
+    }
 
     #[test]
     fn single_thread() {
@@ -37,6 +98,55 @@ mod tests {
         assert_eq!(vec[1], "b");
     }
 
+    #[test]
+    fn extend_with_inaccurate_size_hint_keeps_every_item() {
+        // A safe iterator is allowed to lie about its exact size; the
+        // bulk fast path must not trust `size_hint` to bound iteration,
+        // only to size the allocation.
+        struct Liar {
+            yielded: u32,
+            total: u32,
+        }
+
+        impl Iterator for Liar {
+            type Item = u32;
+
+            fn next(&mut self) -> Option<u32> {
+                if self.yielded < self.total {
+                    self.yielded += 1;
+                    Some(self.yielded)
+                } else {
+                    None
+                }
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (2, Some(2))
+            }
+        }
+
+        let mut vec = FixedVec::<u32>::new(1);
+        vec.extend(Liar { yielded: 0, total: 4 });
+        assert_eq!(&*vec, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn from_elem_zero_fast_path() {
+        let vec = FixedVec::from_elem(0u32, 5);
+        assert_eq!(&*vec, [0u32; 5]);
+    }
+
+    #[test]
+    fn from_elem_negative_zero_and_nan_are_not_memset() {
+        // A zeroed allocation would silently turn `-0.0` into `0.0` and
+        // `NaN` into `0.0`; both must go through the normal clone loop.
+        let neg_zero = FixedVec::from_elem(-0.0f64, 3);
+        assert!(neg_zero.iter().all(|v| v.to_bits() == (-0.0f64).to_bits()));
+
+        let nan = FixedVec::from_elem(f64::NAN, 3);
+        assert!(nan.iter().all(|v| v.is_nan()));
+    }
+
     #[test]
     fn concurrent_push() {
         use std::sync::Arc;